@@ -0,0 +1,119 @@
+use actix_web::body::BoxBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::{web, App, Error, HttpResponse, HttpServer, Responder};
+use futures::future::{ok, Ready};
+use serde::Serialize;
+use thiserror::Error;
+
+// API-key middleware applied with `.wrap(...)` to a whole route group.
+pub struct ApiKeyGuard {
+    expected_key: Option<String>,
+}
+
+impl ApiKeyGuard {
+    pub fn new() -> Self {
+        Self {
+            expected_key: std::env::var("API_KEY").ok(),
+        }
+    }
+}
+
+impl<S, B> actix_web::dev::Transform<S, ServiceRequest> for ApiKeyGuard
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiKeyGuardService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ApiKeyGuardService {
+            service,
+            expected_key: self.expected_key.clone(),
+        })
+    }
+}
+
+pub struct ApiKeyGuardService<S> {
+    service: S,
+    expected_key: Option<String>,
+}
+
+impl<S, B> actix_web::dev::Service<ServiceRequest> for ApiKeyGuardService<S>
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        // No key configured: pass every request straight through.
+        let Some(expected) = self.expected_key.clone() else {
+            return Box::pin(self.service.call(req));
+        };
+
+        let presented = req
+            .headers()
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+
+        if presented.as_deref() == Some(expected.as_str()) {
+            Box::pin(self.service.call(req))
+        } else {
+            Box::pin(async move { Err(ApiError::Unauthorized.into()) })
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("unauthorized")]
+    Unauthorized,
+}
+
+#[derive(Serialize)]
+struct ApiErrorBody {
+    msg: String,
+}
+
+impl actix_web::ResponseError for ApiError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            ApiError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        HttpResponse::build(self.status_code()).json(ApiErrorBody {
+            msg: self.to_string(),
+        })
+    }
+}
+
+async fn protected() -> impl Responder {
+    HttpResponse::Ok().body("you're in")
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    HttpServer::new(|| {
+        App::new().service(
+            web::scope("/admin")
+                .wrap(ApiKeyGuard::new())
+                .route("/protected", web::get().to(protected)),
+        )
+    })
+    .bind(("127.0.0.1", 8080))?
+    .run()
+    .await
+}
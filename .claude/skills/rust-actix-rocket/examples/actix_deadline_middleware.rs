@@ -0,0 +1,145 @@
+use actix_web::body::BoxBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::{web, App, Error, HttpResponse, HttpServer, Responder};
+use actix_rt::time::Timeout;
+use tokio::time::error::Elapsed;
+use futures::future::{ok, Ready};
+use pin_project_lite::pin_project;
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use thiserror::Error;
+
+// Races the inner handler against a timer derived from the request's
+// `X-Request-Deadline` header, falling back to `default_timeout`.
+pub struct Deadline {
+    default_timeout: Option<Duration>,
+}
+
+impl Deadline {
+    pub fn new(default_timeout: Option<Duration>) -> Self {
+        Self { default_timeout }
+    }
+}
+
+impl<S, B> actix_web::dev::Transform<S, ServiceRequest> for Deadline
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = DeadlineMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DeadlineMiddleware {
+            service,
+            default_timeout: self.default_timeout,
+        })
+    }
+}
+
+pub struct DeadlineMiddleware<S> {
+    service: S,
+    default_timeout: Option<Duration>,
+}
+
+impl<S, B> actix_web::dev::Service<ServiceRequest> for DeadlineMiddleware<S>
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = DeadlineFuture<S::Future>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let timeout = req
+            .headers()
+            .get("x-request-deadline")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .or(self.default_timeout);
+
+        let future = self.service.call(req);
+
+        match timeout {
+            Some(timeout) => DeadlineFuture::Timed {
+                timeout: actix_rt::time::timeout(timeout, future),
+            },
+            None => DeadlineFuture::Untimed { future },
+        }
+    }
+}
+
+pin_project! {
+    #[project = DeadlineFutureProj]
+    pub enum DeadlineFuture<F> {
+        Timed { #[pin] timeout: Timeout<F> },
+        Untimed { #[pin] future: F },
+    }
+}
+
+impl<F, B> Future for DeadlineFuture<F>
+where
+    F: Future<Output = Result<ServiceResponse<B>, Error>>,
+{
+    type Output = Result<ServiceResponse<B>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            DeadlineFutureProj::Timed { timeout } => match timeout.poll(cx) {
+                Poll::Ready(Ok(result)) => Poll::Ready(result),
+                Poll::Ready(Err(Elapsed { .. })) => Poll::Ready(Err(DeadlineExceeded.into())),
+                Poll::Pending => Poll::Pending,
+            },
+            DeadlineFutureProj::Untimed { future } => future.poll(cx),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("deadline exceeded")]
+pub struct DeadlineExceeded;
+
+#[derive(Serialize)]
+struct DeadlineExceededBody {
+    msg: String,
+}
+
+impl actix_web::ResponseError for DeadlineExceeded {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::GATEWAY_TIMEOUT
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        HttpResponse::build(self.status_code()).json(DeadlineExceededBody {
+            msg: self.to_string(),
+        })
+    }
+}
+
+async fn proxy() -> impl Responder {
+    HttpResponse::Ok().body("upstream response")
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    HttpServer::new(|| {
+        App::new()
+            .wrap(Deadline::new(Some(Duration::from_secs(5))))
+            .route("/media", web::get().to(proxy))
+    })
+    .bind(("127.0.0.1", 8080))?
+    .run()
+    .await
+}
@@ -0,0 +1,111 @@
+use actix_web::body::EitherBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::{web, App, Error, HttpMessage, HttpResponse, HttpServer, Responder};
+use futures::future::{ok, Ready};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+
+// Validates a bearer JWT and injects the decoded claims into request
+// extensions for downstream handlers to extract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: usize,
+}
+
+pub struct JwtAuth {
+    decoding_key: DecodingKey,
+}
+
+impl JwtAuth {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret),
+        }
+    }
+}
+
+impl<S, B> actix_web::dev::Transform<S, ServiceRequest> for JwtAuth
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = JwtAuthMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(JwtAuthMiddleware {
+            service,
+            decoding_key: self.decoding_key.clone(),
+        })
+    }
+}
+
+pub struct JwtAuthMiddleware<S> {
+    service: S,
+    decoding_key: DecodingKey,
+}
+
+impl<S, B> actix_web::dev::Service<ServiceRequest> for JwtAuthMiddleware<S>
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let token = req
+            .headers()
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+
+        let claims = token.and_then(|token| {
+            decode::<Claims>(token, &self.decoding_key, &Validation::new(Algorithm::HS256))
+                .ok()
+                .map(|data| data.claims)
+        });
+
+        match claims {
+            Some(claims) => {
+                req.extensions_mut().insert(claims);
+                let fut = self.service.call(req);
+                Box::pin(async move { fut.await.map(ServiceResponse::map_into_left_body) })
+            }
+            None => {
+                let (req, _) = req.into_parts();
+                let response = HttpResponse::Forbidden()
+                    .json(serde_json::json!({ "msg": "forbidden" }))
+                    .map_into_right_body();
+                Box::pin(async move { Ok(ServiceResponse::new(req, response)) })
+            }
+        }
+    }
+}
+
+async fn whoami(claims: web::ReqData<Claims>) -> impl Responder {
+    HttpResponse::Ok().body(format!("hello, {}", claims.sub))
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    HttpServer::new(|| {
+        App::new()
+            .wrap(JwtAuth::new(b"jwt-secret"))
+            .route("/whoami", web::get().to(whoami))
+    })
+    .bind(("127.0.0.1", 8080))?
+    .run()
+    .await
+}
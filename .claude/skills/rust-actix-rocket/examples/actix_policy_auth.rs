@@ -0,0 +1,145 @@
+use actix_web::dev::Payload;
+use actix_web::{web, App, FromRequest, HttpRequest, HttpResponse, HttpServer, Responder};
+use futures::future::{err, LocalBoxFuture};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+// Pluggable role-based guards, checked via a handler's extractor arguments.
+pub trait Policy: 'static {
+    fn authenticate(token: &[u8]) -> bool;
+}
+
+pub struct AdminPolicy;
+pub struct PrivatePolicy;
+pub struct PublicPolicy;
+
+pub struct AppState {
+    auth: AuthConfig,
+}
+
+pub enum AuthConfig {
+    NoAuth,
+    Auth(Policies),
+}
+
+impl AuthConfig {
+    fn accepts<P: Policy>(&self, token: &[u8]) -> bool {
+        match self {
+            AuthConfig::NoAuth => true,
+            AuthConfig::Auth(policies) => policies.accepts::<P>(token),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Policies {
+    tokens: HashMap<TypeId, HashSet<Vec<u8>>>,
+}
+
+impl Policies {
+    pub fn grant<P: Policy>(&mut self, token: impl Into<Vec<u8>>) {
+        self.tokens.entry(TypeId::of::<P>()).or_default().insert(token.into());
+    }
+
+    fn accepts<P: Policy>(&self, token: &[u8]) -> bool {
+        P::authenticate(token)
+            || self
+                .tokens
+                .get(&TypeId::of::<P>())
+                .is_some_and(|tokens| tokens.contains(token))
+    }
+}
+
+// Grants a single key to several policies at once.
+#[macro_export]
+macro_rules! create_users {
+    ($policies:expr, $token:expr => { $($policy:ty),+ $(,)? }) => {
+        $( $policies.grant::<$policy>($token); )+
+    };
+}
+
+pub struct GuardedData<P: Policy, T> {
+    pub data: T,
+    _policy: PhantomData<P>,
+}
+
+impl<P: Policy, T: FromRequest + 'static> FromRequest for GuardedData<P, T> {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let Some(state) = req.app_data::<web::Data<AppState>>() else {
+            return Box::pin(err(actix_web::error::ErrorInternalServerError(
+                "missing app state",
+            )));
+        };
+
+        let token = req
+            .headers()
+            .get("x-api-key")
+            .map(|v| v.as_bytes().to_vec())
+            .unwrap_or_default();
+
+        if !state.auth.accepts::<P>(&token) {
+            return Box::pin(err(actix_web::error::ErrorForbidden("forbidden")));
+        }
+
+        let fut = T::from_request(req, payload);
+        Box::pin(async move {
+            match fut.await {
+                Ok(data) => Ok(GuardedData {
+                    data,
+                    _policy: PhantomData,
+                }),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+}
+
+impl Policy for AdminPolicy {
+    fn authenticate(_token: &[u8]) -> bool {
+        false
+    }
+}
+
+impl Policy for PrivatePolicy {
+    fn authenticate(_token: &[u8]) -> bool {
+        false
+    }
+}
+
+impl Policy for PublicPolicy {
+    fn authenticate(_token: &[u8]) -> bool {
+        true
+    }
+}
+
+async fn purge_cache(_data: GuardedData<AdminPolicy, web::Data<AppState>>) -> impl Responder {
+    HttpResponse::Ok().body("cache purged")
+}
+
+async fn read_public(_data: GuardedData<PublicPolicy, web::Data<AppState>>) -> impl Responder {
+    HttpResponse::Ok().body("public data")
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let mut policies = Policies::default();
+    create_users!(policies, "master-key".as_bytes() => { AdminPolicy, PrivatePolicy, PublicPolicy });
+
+    let state = web::Data::new(AppState {
+        auth: AuthConfig::Auth(policies),
+    });
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .route("/admin/purge", web::post().to(purge_cache))
+            .route("/public", web::get().to(read_public))
+    })
+    .bind(("127.0.0.1", 8080))?
+    .run()
+    .await
+}
@@ -0,0 +1,85 @@
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::{web, App, Error, HttpResponse, HttpServer, Responder};
+use futures::future::{ok, Ready};
+use std::future::Future;
+use std::pin::Pin;
+use tracing::info_span;
+use tracing_futures::Instrument;
+use uuid::Uuid;
+
+// Opens a span per request carrying a generated id, method and path.
+pub struct Tracing;
+
+impl<S, B> actix_web::dev::Transform<S, ServiceRequest> for Tracing
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = TracingMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(TracingMiddleware { service })
+    }
+}
+
+pub struct TracingMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> actix_web::dev::Service<ServiceRequest> for TracingMiddleware<S>
+where
+    S: actix_web::dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = Uuid::new_v4();
+        let span = info_span!(
+            "request",
+            method = %req.method(),
+            path = %req.path(),
+            request_id = %request_id,
+        );
+
+        let fut = self.service.call(req);
+        Box::pin(
+            async move {
+                let mut res = fut.await?;
+                res.headers_mut().insert(
+                    HeaderName::from_static("x-request-id"),
+                    HeaderValue::from_str(&request_id.to_string())
+                        .unwrap_or_else(|_| HeaderValue::from_static("invalid")),
+                );
+                Ok(res)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+async fn index() -> impl Responder {
+    tracing::info!("handling index request");
+    HttpResponse::Ok().body("Hello from Actix Web with Tracing!")
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    HttpServer::new(|| App::new().wrap(Tracing).route("/", web::get().to(index)))
+        .bind(("127.0.0.1", 8080))?
+        .run()
+        .await
+}